@@ -13,7 +13,11 @@ Let Ident(x) Equal Int(5)
 
 */
 
+use std::char;
 use std::fmt::{Formatter, FormatError, Show};
+use std::iter::Peekable;
+use std::num;
+use std::str::CharIndices;
 
 #[deriving(Show)]
 pub enum Token {
@@ -33,8 +37,14 @@ pub enum Token {
    Times,
    Divide,
    Equal,
+   EqEq,
+   Bang,
+   NotEq,
    Less,
+   LessEq,
    Great,
+   GreatEq,
+   Arrow,
    Period,
    Comma,
    Newline,
@@ -46,12 +56,35 @@ pub enum Token {
 }
 
 pub enum LexerErrorKind {
-   EndOfData,
    UnmatchedToken,
    IntegerOverflow
 }
 
-pub struct Lexer;
+#[deriving(Show)]
+pub struct Span {
+   pub start_line: uint,
+   pub start_col: uint,
+   pub end_line: uint,
+   pub end_col: uint
+}
+
+impl Span {
+   #[inline]
+   fn new(start_line: uint, start_col: uint, end_line: uint, end_col: uint) -> Span {
+      Span {
+         start_line: start_line,
+         start_col: start_col,
+         end_line: end_line,
+         end_col: end_col
+      }
+   }
+}
+
+pub struct Lexer<'a> {
+   chars: Peekable<(uint, char), CharIndices<'a>>,
+   line: uint,
+   col: uint
+}
 
 pub struct LexerError {
    kind: LexerErrorKind,
@@ -60,165 +93,433 @@ pub struct LexerError {
 
 pub type LexerResult<T> = Result<T, LexerError>;
 
-impl Lexer {
+impl<'a> Lexer<'a> {
    #[inline]
-   pub fn new() -> Lexer {
-      Lexer
+   pub fn new(code: &'a str) -> Lexer<'a> {
+      Lexer {
+         chars: code.char_indices().peekable(),
+         line: 1,
+         col: 1
+      }
    }
 
-   pub fn tokenize(&self, code: &str) -> LexerResult<Vec<Token>> {
+   pub fn tokenize(&mut self) -> LexerResult<Vec<(Token, Span)>> {
       let mut result = vec!();
 
-      let len = code.len();
-      let mut idx = 0;
-      let mut line = 1;
       loop {
-         let (token, index, new_line) = match self.find_token(code, idx, len, line) {
-            Ok(m) => m,
-            Err(LexerError { kind: EndOfData, .. }) => break,
+         match self.next_token() {
+            Ok(Some(m)) => result.push(m),
+            Ok(None) => break,
             Err(f) => return Err(f)
-         };
-         idx = index;
-         line = new_line;
-         result.push(token);
+         }
       }
 
       Ok(result)
    }
 
-   #[inline]
-   fn find_token(&self, code: &str, mut idx: uint, len: uint, mut line: uint) -> LexerResult<(Token, uint, uint)> {
-      idx = self.skip_whitespace(code, idx);
-      if idx < len {
-         let val = match self.find_symbol_token(code, idx, line) {
-            Some(result) => match result {
-               Ok((token, index, new_line)) => {
-                  idx = index;
-                  line = new_line;
-                  token
+   // like tokenize, but records every error instead of bailing on the first
+   pub fn tokenize_all(&mut self) -> (Vec<(Token, Span)>, Vec<LexerError>) {
+      let mut result = vec!();
+      let mut errors = vec!();
+
+      loop {
+         self.skip_whitespace();
+         let in_string = self.peek_char() == Some('"');
+         match self.next_token() {
+            Ok(Some(m)) => result.push(m),
+            Ok(None) => break,
+            Err(f) => {
+               if !in_string {
+                  self.recover();
                }
+               errors.push(f);
+            }
+         }
+      }
+
+      (result, errors)
+   }
+
+   fn next_token(&mut self) -> LexerResult<Option<(Token, Span)>> {
+      self.skip_whitespace();
+
+      let ch = match self.peek_char() {
+         Some(ch) => ch,
+         None => return Ok(None)
+      };
+
+      let start_line = self.line;
+      let start_col = self.col;
+
+      let token = match self.scan_symbol(ch) {
+         Some(result) => match result {
+            Ok(token) => token,
+            Err(f) => return Err(f)
+         },
+         None => match ch {
+            '0'..'9' => match self.scan_number(ch) {
+               Ok(token) => token,
                Err(f) => return Err(f)
             },
-            None => match code.char_at(idx) {
-               ch @ '0'..'9' => {
-                  // number
-                  // TODO: handle floats
-                  let mut buffer = String::new();
+            _ => self.scan_ident(ch)
+         }
+      };
+
+      Ok(Some((token, Span::new(start_line, start_col, self.line, self.col))))
+   }
+
+   // skips to the next whitespace/newline after a bad token; a bad string
+   // is already consumed past its closing quote, so there's nothing to skip
+   #[inline]
+   fn recover(&mut self) {
+      loop {
+         match self.peek_char() {
+            Some(ch) if ch == '\n' || self.is_whitespace(ch) => break,
+            Some(_) => { self.bump(); }
+            None => break
+         }
+      }
+   }
+
+   #[inline]
+   fn scan_number(&mut self, first: char) -> LexerResult<Token> {
+      let line = self.line;
+
+      if first == '0' {
+         match self.peek2_char() {
+            Some(marker) if marker == 'x' || marker == 'o' || marker == 'b' => {
+               self.bump(); // '0'
+               self.bump(); // marker
+               return self.scan_radix_int(marker, line);
+            }
+            _ => {}
+         }
+      }
+
+      let mut buffer = String::new();
+      buffer.push_char(first);
+      self.bump();
+
+      loop {
+         match self.peek_char() {
+            Some(ch) if ch == '_' => { self.bump(); }
+            Some(ch) if ch.is_digit() => {
+               buffer.push_char(ch);
+               self.bump();
+            }
+            _ => break
+         }
+      }
+
+      let mut is_float = false;
+
+      // a '.' only introduces a fractional part if followed by a digit,
+      // otherwise it is a Period token and `3.foo` should lex as
+      // Int(3) Period Ident(foo)
+      if self.peek_char() == Some('.') && self.peek2_char().map_or(false, |ch| ch.is_digit()) {
+         is_float = true;
+         buffer.push_char('.');
+         self.bump();
+         loop {
+            match self.peek_char() {
+               Some(ch) if ch.is_digit() => {
                   buffer.push_char(ch);
-                  for ch in code.slice_from(idx + 1).chars() {
-                     if ch.is_digit() {
-                        buffer.push_char(ch);
-                     } else {
-                        break;
-                     }
-                  }
-                  idx += buffer.len();
-                  Int(match from_str(buffer.as_slice()) {
-                     Some(m) => m,
-                     None => return Err(LexerError::new(IntegerOverflow, Some(format!("'{}' at line {} is too big", buffer, line))))
-                  })
+                  self.bump();
                }
-               ch => {
-                  // ident
-                  let mut buffer = String::new();
-                  buffer.push_char(ch);
-                  idx += 1;
-                  for ch in code.slice_from(idx).chars() {
-                     if ch.is_whitespace() || ch == '"' || self.find_symbol_token(code, idx, line).is_some() {
-                        break;
-                     } else {
-                        buffer.push_char(ch);
-                        idx += 1;
-                     }
-                  }
-                  match buffer.as_slice() {
-                     "if" => If,
-                     "loop" => Loop,
-                     "break" => Break,
-                     "continue" => Continue,
-                     "let" => Let,
-                     "fn" => Fn,
-                     "macro" => Macro,
-                     "true" => Bool(true),
-                     "false" => Bool(false),
-                     _ => Ident(buffer)
+               _ => break
+            }
+         }
+      }
+
+      match self.peek_char() {
+         Some(marker) if marker == 'e' || marker == 'E' => {
+            self.bump();
+            let mut exp = String::new();
+            match self.peek_char() {
+               Some(sign) if sign == '+' || sign == '-' => {
+                  exp.push_char(sign);
+                  self.bump();
+               }
+               _ => {}
+            }
+            let digits_start = exp.len();
+            loop {
+               match self.peek_char() {
+                  Some(ch) if ch.is_digit() => {
+                     exp.push_char(ch);
+                     self.bump();
                   }
+                  _ => break
                }
             }
-         };
-         Ok((val, idx, line))
+            if exp.len() == digits_start {
+               return Err(LexerError::new(UnmatchedToken, Some(format!("malformed exponent '{}{}' at line {}", marker, exp, line))));
+            }
+            is_float = true;
+            buffer.push_char(marker);
+            buffer.push_str(exp.as_slice());
+         }
+         _ => {}
+      }
+
+      if is_float {
+         match from_str::<f64>(buffer.as_slice()) {
+            Some(m) => Ok(Float(m)),
+            None => Err(LexerError::new(IntegerOverflow, Some(format!("'{}' at line {} is too big", buffer, line))))
+         }
       } else {
-         Err(LexerError::new(EndOfData, None))
+         match from_str::<i64>(buffer.as_slice()) {
+            Some(m) => Ok(Int(m)),
+            None => Err(LexerError::new(IntegerOverflow, Some(format!("'{}' at line {} is too big", buffer, line))))
+         }
       }
    }
 
+   // '0' and the radix marker are already consumed
    #[inline]
-   fn find_symbol_token(&self, code: &str, mut idx: uint, mut line: uint) -> Option<LexerResult<(Token, uint, uint)>> {
-      let val = match code.char_at(idx) {
+   fn scan_radix_int(&mut self, marker: char, line: uint) -> LexerResult<Token> {
+      let radix = match marker {
+         'x' => 16u,
+         'o' => 8u,
+         _ => 2u
+      };
+
+      let mut digits = String::new();
+      loop {
+         match self.peek_char() {
+            Some(ch) if ch == '_' => { self.bump(); }
+            Some(ch) if self.is_digit_radix(ch, radix) => {
+               digits.push_char(ch);
+               self.bump();
+            }
+            _ => break
+         }
+      }
+
+      if digits.len() == 0 {
+         return Err(LexerError::new(UnmatchedToken, Some(format!("'0{}' at line {} has no digits", marker, line))));
+      }
+
+      match num::from_str_radix::<i64>(digits.as_slice(), radix) {
+         Some(m) => Ok(Int(m)),
+         None => Err(LexerError::new(IntegerOverflow, Some(format!("'0{}{}' at line {} is too big", marker, digits, line))))
+      }
+   }
+
+   #[inline]
+   fn is_digit_radix(&self, ch: char, radix: uint) -> bool {
+      match ch.to_digit(radix) {
+         Some(_) => true,
+         None => false
+      }
+   }
+
+   #[inline]
+   fn scan_ident(&mut self, first: char) -> Token {
+      let mut buffer = String::new();
+      buffer.push_char(first);
+      self.bump();
+
+      loop {
+         match self.peek_char() {
+            Some(ch) if ch.is_whitespace() || self.is_symbol_start(ch) => break,
+            Some(ch) => {
+               buffer.push_char(ch);
+               self.bump();
+            }
+            None => break
+         }
+      }
+
+      match buffer.as_slice() {
+         "if" => If,
+         "loop" => Loop,
+         "break" => Break,
+         "continue" => Continue,
+         "let" => Let,
+         "fn" => Fn,
+         "macro" => Macro,
+         "true" => Bool(true),
+         "false" => Bool(false),
+         _ => Ident(buffer)
+      }
+   }
+
+   #[inline]
+   fn scan_symbol(&mut self, ch: char) -> Option<LexerResult<Token>> {
+      match ch {
          '"' => {
-            idx += 1;
-            let start = idx;
+            let line = self.line;
+            self.bump();
+            let mut buffer = String::new();
             loop {
-               match code.slice_from(idx).find('"') {
-                  Some(index) => {
-                     let mut count = 0u;
-                     for ch in code.slice(idx, index).chars().rev() {
-                        if ch == '\\' {
-                           count += 1;
-                        } else {
-                           break;
-                        }
-                     }
-                     if count % 2 == 0 {
-                        idx = index;
-                        break;
-                     } else {
-                        idx = index + 1;
+               match self.bump() {
+                  Some((_, '"')) => return Some(Ok(Str(buffer))),
+                  Some((_, '\\')) => match self.decode_escape(line) {
+                     Ok(ch) => buffer.push_char(ch),
+                     Err(f) => {
+                        // consume the rest of the string so `recover()`'s
+                        // "nothing left to skip" assumption still holds
+                        self.skip_rest_of_string();
+                        return Some(Err(f));
                      }
-                  }
+                  },
+                  Some((_, c)) => buffer.push_char(c),
                   None => return Some(Err(LexerError::new(UnmatchedToken, Some(format!("mismatched '\"' starting at line {}", line)))))
                }
             }
-            Str(code.slice(start, idx + 1).to_string())
-         }
-         '(' => LParen,
-         ')' => RParen,
-         '{' => LBrace,
-         '}' => RBrace,
-         '+' => Plus,
-         '-' => Minus,
-         '*' => Times,
-         '/' => Divide,
-         '=' => Equal,
-         '<' => Less,
-         '>' => Great,
-         '.' => Period,
-         ',' => Comma,
-         '\n' => {
-            line += 1;
-            Newline
-         }
-         _ => return None
-      };
-      Some(Ok((val, idx + 1, line)))
+         }
+         '(' => { self.bump(); Some(Ok(LParen)) }
+         ')' => { self.bump(); Some(Ok(RParen)) }
+         '{' => { self.bump(); Some(Ok(LBrace)) }
+         '}' => { self.bump(); Some(Ok(RBrace)) }
+         '+' => { self.bump(); Some(Ok(Plus)) }
+         '-' => {
+            self.bump();
+            if self.bump_if('>') { Some(Ok(Arrow)) } else { Some(Ok(Minus)) }
+         }
+         '*' => { self.bump(); Some(Ok(Times)) }
+         '/' => { self.bump(); Some(Ok(Divide)) }
+         '=' => {
+            self.bump();
+            if self.bump_if('=') { Some(Ok(EqEq)) } else { Some(Ok(Equal)) }
+         }
+         '!' => {
+            self.bump();
+            if self.bump_if('=') { Some(Ok(NotEq)) } else { Some(Ok(Bang)) }
+         }
+         '<' => {
+            self.bump();
+            if self.bump_if('=') { Some(Ok(LessEq)) } else { Some(Ok(Less)) }
+         }
+         '>' => {
+            self.bump();
+            if self.bump_if('=') { Some(Ok(GreatEq)) } else { Some(Ok(Great)) }
+         }
+         '.' => { self.bump(); Some(Ok(Period)) }
+         ',' => { self.bump(); Some(Ok(Comma)) }
+         '\n' => { self.bump(); Some(Ok(Newline)) }
+         _ => None
+      }
+   }
+
+   // the '\' is already consumed
+   #[inline]
+   fn decode_escape(&mut self, line: uint) -> LexerResult<char> {
+      match self.bump() {
+         Some((_, 'n')) => Ok('\n'),
+         Some((_, 't')) => Ok('\t'),
+         Some((_, 'r')) => Ok('\r'),
+         Some((_, '\\')) => Ok('\\'),
+         Some((_, '"')) => Ok('"'),
+         Some((_, '0')) => Ok('\0'),
+         Some((_, 'u')) => self.decode_unicode_escape(line),
+         Some((_, c)) => Err(LexerError::new(UnmatchedToken, Some(format!("unknown escape '\\{}' at line {}", c, line)))),
+         None => Err(LexerError::new(UnmatchedToken, Some(format!("mismatched '\"' starting at line {}", line))))
+      }
+   }
+
+   // the 'u' is already consumed
+   #[inline]
+   fn decode_unicode_escape(&mut self, line: uint) -> LexerResult<char> {
+      match self.bump() {
+         Some((_, '{')) => {}
+         _ => return Err(LexerError::new(UnmatchedToken, Some(format!("malformed unicode escape at line {}", line))))
+      }
+
+      let mut digits = String::new();
+      loop {
+         match self.bump() {
+            Some((_, '}')) => break,
+            Some((_, c)) => digits.push_char(c),
+            None => return Err(LexerError::new(UnmatchedToken, Some(format!("malformed unicode escape at line {}", line))))
+         }
+      }
+
+      match num::from_str_radix::<u32>(digits.as_slice(), 16u) {
+         Some(code) => match char::from_u32(code) {
+            Some(ch) => Ok(ch),
+            None => Err(LexerError::new(UnmatchedToken, Some(format!("invalid unicode escape '\\u{{{}}}' at line {}", digits, line))))
+         },
+         None => Err(LexerError::new(UnmatchedToken, Some(format!("invalid unicode escape '\\u{{{}}}' at line {}", digits, line))))
+      }
+   }
+
+   // leaves the stream past the closing quote (or at end-of-input), same
+   // as a successfully-scanned or genuinely unterminated string would
+   #[inline]
+   fn skip_rest_of_string(&mut self) {
+      loop {
+         match self.bump() {
+            Some((_, '"')) => break,
+            Some((_, '\\')) => { self.bump(); }
+            Some(_) => {}
+            None => break
+         }
+      }
    }
 
    #[inline]
-   fn skip_whitespace(&self, code: &str, mut idx: uint) -> uint {
-      for ch in code.slice_from(idx).chars() {
-         if self.is_whitespace(ch) {
-            idx += 1;
-         } else {
-            break;
+   fn is_symbol_start(&self, ch: char) -> bool {
+      match ch {
+         '"' | '(' | ')' | '{' | '}' | '+' | '-' | '*' | '/' | '=' | '!' | '<' | '>' | '.' | ',' | '\n' => true,
+         _ => false
+      }
+   }
+
+   #[inline]
+   fn bump_if(&mut self, expected: char) -> bool {
+      match self.peek_char() {
+         Some(ch) if ch == expected => {
+            self.bump();
+            true
+         }
+         _ => false
+      }
+   }
+
+   #[inline]
+   fn skip_whitespace(&mut self) {
+      loop {
+         match self.peek_char() {
+            Some(ch) if self.is_whitespace(ch) => { self.bump(); }
+            _ => break
          }
       }
-      idx
    }
 
    #[inline]
    fn is_whitespace(&self, ch: char) -> bool {
       ch == '\t' || ch == ' '
    }
+
+   #[inline]
+   fn peek_char(&mut self) -> Option<char> {
+      self.chars.peek().map(|&(_, ch)| ch)
+   }
+
+   #[inline]
+   fn peek2_char(&self) -> Option<char> {
+      let mut chars = self.chars.clone();
+      chars.next();
+      chars.peek().map(|&(_, ch)| ch)
+   }
+
+   #[inline]
+   fn bump(&mut self) -> Option<(uint, char)> {
+      match self.chars.next() {
+         Some((idx, ch)) => {
+            if ch == '\n' {
+               self.line += 1;
+               self.col = 1;
+            } else {
+               self.col += 1;
+            }
+            Some((idx, ch))
+         }
+         None => None
+      }
+   }
 }
 
 impl LexerError {
@@ -228,6 +529,11 @@ impl LexerError {
          desc: desc
       }
    }
+
+   #[inline]
+   pub fn kind(&self) -> &LexerErrorKind {
+      &self.kind
+   }
 }
 
 impl Show for LexerError {
@@ -238,3 +544,176 @@ impl Show for LexerError {
       }
    }
 }
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   #[test]
+   fn unicode_escape_decodes() {
+      let mut lexer = Lexer::new("\"\\u{41}\"");
+      let tokens = match lexer.tokenize() {
+         Ok(m) => m,
+         Err(f) => fail!(f)
+      };
+      match tokens[0].0 {
+         Str(ref s) => assert_eq!(s.as_slice(), "A"),
+         _ => fail!("expected Str")
+      }
+   }
+
+   #[test]
+   fn unicode_escape_rejects_bad_codepoint() {
+      let mut lexer = Lexer::new("\"\\u{d800}\"");
+      assert!(lexer.tokenize().is_err());
+   }
+
+   #[test]
+   fn unknown_escape_recovers_without_losing_later_tokens() {
+      let mut lexer = Lexer::new("\"hello \\z world\" ident2");
+      let (tokens, errors) = lexer.tokenize_all();
+      assert_eq!(errors.len(), 1);
+      assert_eq!(tokens.len(), 1);
+      match tokens[0].0 {
+         Ident(ref s) => assert_eq!(s.as_slice(), "ident2"),
+         _ => fail!("expected Ident")
+      }
+      match errors[0].kind() {
+         &UnmatchedToken => {}
+         _ => fail!("expected UnmatchedToken")
+      }
+   }
+
+   #[test]
+   fn hex_int_with_separators() {
+      let mut lexer = Lexer::new("0xFF_FF");
+      let tokens = match lexer.tokenize() {
+         Ok(m) => m,
+         Err(f) => fail!(f)
+      };
+      match tokens[0].0 {
+         Int(n) => assert_eq!(n, 0xFFFF),
+         _ => fail!("expected Int")
+      }
+   }
+
+   #[test]
+   fn decimal_int_with_separators() {
+      let mut lexer = Lexer::new("1_000_000");
+      let tokens = match lexer.tokenize() {
+         Ok(m) => m,
+         Err(f) => fail!(f)
+      };
+      match tokens[0].0 {
+         Int(n) => assert_eq!(n, 1000000),
+         _ => fail!("expected Int")
+      }
+   }
+
+   #[test]
+   fn bare_hex_prefix_is_an_error() {
+      let mut lexer = Lexer::new("0x");
+      assert!(lexer.tokenize().is_err());
+   }
+
+   #[test]
+   fn malformed_exponent_is_an_error() {
+      let mut lexer = Lexer::new("1e");
+      assert!(lexer.tokenize().is_err());
+   }
+
+   #[test]
+   fn trailing_period_without_digit_stays_period() {
+      let mut lexer = Lexer::new("3.foo");
+      let tokens = match lexer.tokenize() {
+         Ok(m) => m,
+         Err(f) => fail!(f)
+      };
+      assert_eq!(tokens.len(), 3);
+      match tokens[0].0 { Int(n) => assert_eq!(n, 3), _ => fail!("expected Int") }
+      match tokens[1].0 { Period => {}, _ => fail!("expected Period") }
+      match tokens[2].0 {
+         Ident(ref s) => assert_eq!(s.as_slice(), "foo"),
+         _ => fail!("expected Ident")
+      }
+   }
+
+   #[test]
+   fn float_literal_lexes() {
+      let mut lexer = Lexer::new("3.14");
+      let tokens = match lexer.tokenize() {
+         Ok(m) => m,
+         Err(f) => fail!(f)
+      };
+      assert_eq!(tokens.len(), 1);
+      match tokens[0].0 {
+         Float(n) => assert_eq!(n, 3.14f64),
+         _ => fail!("expected Float")
+      }
+   }
+
+   #[test]
+   fn float_literal_with_exponent_lexes() {
+      let mut lexer = Lexer::new("2.0e-5");
+      let tokens = match lexer.tokenize() {
+         Ok(m) => m,
+         Err(f) => fail!(f)
+      };
+      assert_eq!(tokens.len(), 1);
+      match tokens[0].0 {
+         Float(n) => assert_eq!(n, 2.0e-5f64),
+         _ => fail!("expected Float")
+      }
+   }
+
+   #[test]
+   fn tokenize_all_recovers_past_a_bad_radix_literal() {
+      let mut lexer = Lexer::new("0xg foo");
+      let (tokens, errors) = lexer.tokenize_all();
+      assert_eq!(errors.len(), 1);
+      assert_eq!(tokens.len(), 1);
+      match tokens[0].0 {
+         Ident(ref s) => assert_eq!(s.as_slice(), "foo"),
+         _ => fail!("expected Ident")
+      }
+   }
+
+   #[test]
+   fn span_tracks_single_line_token_position() {
+      let mut lexer = Lexer::new("  foo");
+      let tokens = match lexer.tokenize() {
+         Ok(m) => m,
+         Err(f) => fail!(f)
+      };
+      assert_eq!(tokens[0].1.start_line, 1);
+      assert_eq!(tokens[0].1.start_col, 3);
+      assert_eq!(tokens[0].1.end_line, 1);
+      assert_eq!(tokens[0].1.end_col, 6);
+   }
+
+   #[test]
+   fn span_tracks_multi_line_token_position() {
+      let mut lexer = Lexer::new("\"ab\ncd\"");
+      let tokens = match lexer.tokenize() {
+         Ok(m) => m,
+         Err(f) => fail!(f)
+      };
+      assert_eq!(tokens[0].1.start_line, 1);
+      assert_eq!(tokens[0].1.start_col, 1);
+      assert_eq!(tokens[0].1.end_line, 2);
+      assert_eq!(tokens[0].1.end_col, 4);
+   }
+
+   #[test]
+   fn multi_char_operators_lex_as_single_tokens() {
+      let mut lexer = Lexer::new("<= == ->");
+      let tokens = match lexer.tokenize() {
+         Ok(m) => m,
+         Err(f) => fail!(f)
+      };
+      assert_eq!(tokens.len(), 3);
+      match tokens[0].0 { LessEq => {}, _ => fail!("expected LessEq") }
+      match tokens[1].0 { EqEq => {}, _ => fail!("expected EqEq") }
+      match tokens[2].0 { Arrow => {}, _ => fail!("expected Arrow") }
+   }
+}
@@ -1,8 +1,8 @@
 mod lexer;
 
 fn main() {
-   let lexer = lexer::Lexer::new();
-   match lexer.tokenize("fn main() { 3 + 4 }") {
+   let mut lexer = lexer::Lexer::new("fn main() { 3 + 4 }");
+   match lexer.tokenize() {
       Ok(m) => println!("{}", m),
       Err(f) => fail!(f)
    }